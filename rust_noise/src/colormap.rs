@@ -0,0 +1,85 @@
+/// A gradient look-up table mapping a noise value in `[0, 1]` to an RGB
+/// color, used by the exporter to turn grayscale noise into a usable
+/// texture/heightmap.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Colormap {
+    Grayscale,
+    Terrain,
+    Heat,
+    /// User-defined gradient stops, each `(position, color)` with
+    /// `position` in `[0, 1]`. Stops are sorted by position before
+    /// sampling; with no stops, every value maps to black.
+    Custom(Vec<(f64, [u8; 3])>),
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Colormap::Grayscale
+    }
+}
+
+const TERRAIN_STOPS: [(f64, [u8; 3]); 5] = [
+    (0.0, [0, 51, 102]),
+    (0.3, [0, 153, 204]),
+    (0.35, [194, 178, 128]),
+    (0.5, [34, 139, 34]),
+    (0.75, [101, 67, 33]),
+];
+
+const HEAT_STOPS: [(f64, [u8; 3]); 4] = [
+    (0.0, [0, 0, 0]),
+    (0.35, [128, 0, 0]),
+    (0.7, [255, 165, 0]),
+    (1.0, [255, 255, 224]),
+];
+
+impl Colormap {
+    pub fn sample(&self, t: f64) -> [u8; 3] {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Colormap::Grayscale => {
+                let c = (t * 255.0).round() as u8;
+                [c, c, c]
+            }
+            Colormap::Terrain => lerp_stops(&TERRAIN_STOPS, t),
+            Colormap::Heat => lerp_stops(&HEAT_STOPS, t),
+            Colormap::Custom(stops) => lerp_stops(stops, t),
+        }
+    }
+}
+
+fn lerp_stops(stops: &[(f64, [u8; 3])], t: f64) -> [u8; 3] {
+    if stops.is_empty() {
+        return [0, 0, 0];
+    }
+
+    let mut sorted = stops.to_vec();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    if t <= sorted[0].0 {
+        return sorted[0].1;
+    }
+    if t >= sorted[sorted.len() - 1].0 {
+        return sorted[sorted.len() - 1].1;
+    }
+
+    for window in sorted.windows(2) {
+        let (p0, c0) = window[0];
+        let (p1, c1) = window[1];
+        if t >= p0 && t <= p1 {
+            let span = (p1 - p0).max(f64::EPSILON);
+            let local_t = (t - p0) / span;
+            return [
+                lerp_u8(c0[0], c1[0], local_t),
+                lerp_u8(c0[1], c1[1], local_t),
+                lerp_u8(c0[2], c1[2], local_t),
+            ];
+        }
+    }
+
+    sorted[sorted.len() - 1].1
+}
+
+fn lerp_u8(a: u8, b: u8, t: f64) -> u8 {
+    (a as f64 + (b as f64 - a as f64) * t).round() as u8
+}
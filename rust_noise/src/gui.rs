@@ -1,24 +1,60 @@
+use crate::colormap::Colormap;
 use crate::noise::{Noise, NoiseType};
+use crate::palette;
 use eframe::{egui, epi};
 use image::{ImageBuffer, Rgba};
-use noise::{
-    self,
-    NoiseFn,
-    Point2,
-};
+
+/// How per-octave samples are combined in the fBm accumulation loop,
+/// mirroring the two stitch modes in SVG/Skia's Perlin shader.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FractalMode {
+    /// Sum signed octaves and remap to `[0, 1]`: smooth, cloud-like noise.
+    Fractal,
+    /// Sum `|octave|` and use the raw total: sharp flame/marble creases.
+    Turbulence,
+}
+
+impl Default for FractalMode {
+    fn default() -> Self {
+        FractalMode::Fractal
+    }
+}
 
 pub struct NoiseGui {
     noise: Noise,
     texture: Option<egui::TextureHandle>,
     seed: u32,
+    seamless: bool,
+    tile_period: i32,
+    fractal_mode: FractalMode,
+    colormap: Colormap,
+    quantize: bool,
+    palette_size: u32,
+    use_3d: bool,
+    time: f64,
+    loop_period: f64,
+    loop_radius: f64,
+    loop_frames: u32,
 }
 
 impl Default for NoiseGui {
     fn default() -> Self {
+        let seed = 0;
         Self {
-            noise: Noise::default(),
+            noise: Noise::from_seed(seed as u64),
             texture: None,
-            seed: 0,
+            seed,
+            seamless: false,
+            tile_period: 4,
+            fractal_mode: FractalMode::default(),
+            colormap: Colormap::default(),
+            quantize: false,
+            palette_size: 256,
+            use_3d: false,
+            time: 0.0,
+            loop_period: 4.0,
+            loop_radius: 1.0,
+            loop_frames: 60,
         }
     }
 }
@@ -59,9 +95,13 @@ impl epi::App for NoiseGui {
                 changed |= ui
                     .add(egui::Slider::new(&mut self.noise.gain, 0.0..=1.0).text("Gain"))
                     .changed();
-                changed |= ui
+                let seed_changed = ui
                     .add(egui::Slider::new(&mut self.seed, 0..=1000).text("Seed"))
                     .changed();
+                if seed_changed {
+                    self.noise.reseed(self.seed as u64);
+                }
+                changed |= seed_changed;
 
                 egui::ComboBox::from_label("Noise Type")
                     .selected_text(format!("{:?}", self.noise.noise_type))
@@ -81,12 +121,127 @@ impl epi::App for NoiseGui {
                             NoiseType::Simplex,
                             "Simplex",
                         ).changed();
+                        changed |= ui.selectable_value(
+                            &mut self.noise.noise_type,
+                            NoiseType::ImprovedPerlin,
+                            "Improved Perlin",
+                        ).changed();
+                    });
+
+                // Tiling is only honored by `perlin`/`perlin_3d`/`value`/`value_3d`
+                // (see `Noise::tile`); Simplex and Improved Perlin ignore it.
+                let tileable = matches!(self.noise.noise_type, NoiseType::Value | NoiseType::Perlin);
+                if !tileable {
+                    self.seamless = false;
+                }
+                ui.set_enabled(tileable);
+                changed |= ui.checkbox(&mut self.seamless, "Seamless").changed();
+                ui.set_enabled(true);
+                if !tileable {
+                    ui.label("(tiling needs Value or Perlin noise)");
+                }
+                if self.seamless {
+                    changed |= ui
+                        .add(egui::Slider::new(&mut self.tile_period, 1..=64).text("Tile Period"))
+                        .changed();
+                    ui.label(
+                        "(lacunarity is rounded to a whole number while tiling, \
+                         without changing the slider's stored value)",
+                    );
+                }
+                // X and Y share one period: every render samples both axes
+                // through the same `frequency` scalar (see `get_noise`), so
+                // a single shared period is what `seamless_frequency` can
+                // actually solve for per render size.
+                self.noise.tile = if self.seamless {
+                    Some((self.tile_period, self.tile_period))
+                } else {
+                    None
+                };
+
+                egui::ComboBox::from_label("Fractal Mode")
+                    .selected_text(format!("{:?}", self.fractal_mode))
+                    .show_ui(ui, |ui| {
+                        changed |= ui.selectable_value(
+                            &mut self.fractal_mode,
+                            FractalMode::Fractal,
+                            "Fractal",
+                        ).changed();
+                        changed |= ui.selectable_value(
+                            &mut self.fractal_mode,
+                            FractalMode::Turbulence,
+                            "Turbulence",
+                        ).changed();
+                    });
+
+                egui::ComboBox::from_label("Colormap")
+                    .selected_text(format!("{:?}", self.colormap))
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut self.colormap, Colormap::Grayscale, "Grayscale")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(&mut self.colormap, Colormap::Terrain, "Terrain")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(&mut self.colormap, Colormap::Heat, "Heat")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(
+                                &mut self.colormap,
+                                Colormap::Custom(vec![(0.0, [0, 0, 0]), (1.0, [255, 255, 255])]),
+                                "Custom",
+                            )
+                            .changed();
                     });
 
+                if let Colormap::Custom(stops) = &mut self.colormap {
+                    ui.label("Custom Gradient Stops");
+                    let mut remove_index = None;
+                    for (i, (pos, color)) in stops.iter_mut().enumerate() {
+                        changed |= ui
+                            .add(egui::Slider::new(pos, 0.0..=1.0).text(format!("Stop {} Position", i)))
+                            .changed();
+                        changed |= ui.color_edit_button_srgb(color).changed();
+                        if ui.button(format!("Remove Stop {}", i)).clicked() {
+                            remove_index = Some(i);
+                        }
+                    }
+                    if let Some(i) = remove_index {
+                        stops.remove(i);
+                        changed = true;
+                    }
+                    if ui.button("Add Stop").clicked() {
+                        stops.push((1.0, [255, 255, 255]));
+                        changed = true;
+                    }
+                }
+
+                changed |= ui.checkbox(&mut self.quantize, "Quantize Palette").changed();
+                if self.quantize {
+                    changed |= ui
+                        .add(egui::Slider::new(&mut self.palette_size, 2..=256).text("Palette Size"))
+                        .changed();
+                }
+
+                changed |= ui.checkbox(&mut self.use_3d, "3D").changed();
+                if self.use_3d {
+                    changed |= ui
+                        .add(egui::Slider::new(&mut self.time, 0.0..=self.loop_period).text("Time"))
+                        .changed();
+                    ui.add(egui::Slider::new(&mut self.loop_period, 0.1..=60.0).text("Loop Period"));
+                    ui.add(egui::Slider::new(&mut self.loop_radius, 0.1..=10.0).text("Loop Radius"));
+                    ui.add(egui::Slider::new(&mut self.loop_frames, 1..=240).text("Loop Frames"));
+                }
+
                 if ui.button("Export").clicked() {
                     self.export_wallpaper();
                 }
 
+                if self.use_3d && ui.button("Export Loop").clicked() {
+                    self.export_loop();
+                }
+
                 if let Some(texture) = &self.texture {
                     ui.image(texture, texture.size_vec2());
                 }
@@ -107,8 +262,12 @@ impl NoiseGui {
         let mut image = ImageBuffer::new(size, size);
 
         for (x, y, pixel) in image.enumerate_pixels_mut() {
-            let value = self.get_noise(x as f64, y as f64);
-            let color = ((value + 1.0) / 2.0 * 255.0) as u8;
+            let value = if self.use_3d {
+                self.get_noise_3d(x as f64, y as f64, self.time, size)
+            } else {
+                self.get_noise(x as f64, y as f64, size)
+            };
+            let color = (value * 255.0) as u8;
             *pixel = Rgba([color, color, color, 255]);
         }
 
@@ -120,35 +279,162 @@ impl NoiseGui {
         self.texture = Some(texture);
     }
 
-    fn get_noise(&self, x: f64, y: f64) -> f64 {
+    /// The base frequency to sample a render of `size` pixels at. With
+    /// tiling active, the exported/previewed image only actually repeats
+    /// seamlessly if `size * frequency` lands on a whole multiple of the
+    /// tile period — otherwise the lattice wraps mid-image instead of at
+    /// the edge. Rather than trust the user to hand-tune `frequency` per
+    /// export size, snap it to the nearest multiple that satisfies that
+    /// exactly for this `size`. Different render sizes (256 preview, 1920
+    /// wallpaper, 512 loop frame) therefore each get their own slightly
+    /// different effective frequency; only the requested `frequency` is
+    /// approximate, the period itself is always exact.
+    fn seamless_frequency(&self, size: u32) -> f64 {
+        match self.noise.tile {
+            Some((period, _)) if self.seamless => {
+                let period = period.clamp(1, 256) as f64;
+                let size = size as f64;
+                let repeats = (size * self.noise.frequency / period).round().max(1.0);
+                repeats * period / size
+            }
+            _ => self.noise.frequency,
+        }
+    }
+
+    /// The fBm loop scales the tile period by `lacunarity` per octave to
+    /// stay seamless across octaves; that only lands on whole lattice cells
+    /// when lacunarity is a whole number. Rounding it here (rather than
+    /// writing the rounded value back into `self.noise.lacunarity`) keeps
+    /// the effect scoped to noise evaluation instead of clobbering the
+    /// slider's stored value out from under the user.
+    fn effective_lacunarity(&self) -> f64 {
+        if self.seamless {
+            self.noise.lacunarity.round().max(1.0)
+        } else {
+            self.noise.lacunarity
+        }
+    }
+
+    fn get_noise(&self, x: f64, y: f64, size: u32) -> f64 {
         let point = [x, y];
         let mut value = 0.0;
-        let mut frequency = self.noise.frequency;
+        let mut frequency = self.seamless_frequency(size);
+        let base_frequency = frequency;
+        let mut amplitude = self.noise.gain;
+        let lacunarity = self.effective_lacunarity();
+        // Each octave samples at `frequency * lacunarity^k`, so the tile
+        // period has to grow by the same factor each octave or the lattice
+        // wrap boundary drifts away from the image edge and the export
+        // seams again past octave 0.
+        let mut lattice = self.noise;
+
+        for _ in 0..self.noise.octaves {
+            let sample = match lattice.noise_type {
+                NoiseType::Value => lattice.value(point[0] * frequency, point[1] * frequency),
+                NoiseType::Perlin => lattice.perlin(point[0] * frequency, point[1] * frequency),
+                NoiseType::Simplex => lattice.simplex(point[0] * frequency, point[1] * frequency),
+                NoiseType::ImprovedPerlin => lattice.improved_perlin(point[0] * frequency, point[1] * frequency),
+            };
+            match self.fractal_mode {
+                FractalMode::Fractal => value += sample * amplitude,
+                FractalMode::Turbulence => value += sample.abs() * amplitude,
+            }
+            frequency *= lacunarity;
+            amplitude *= self.noise.persistence;
+            if let Some((px, py)) = self.noise.tile {
+                let scale = (frequency / base_frequency).round().max(1.0) as i32;
+                lattice.tile = Some((px.saturating_mul(scale).min(256), py.saturating_mul(scale).min(256)));
+            }
+        }
+
+        match self.fractal_mode {
+            FractalMode::Fractal => (value + 1.0) / 2.0,
+            FractalMode::Turbulence => value,
+        }
+    }
+
+    /// Like `get_noise`, but samples the 3D variants with `z` treated as
+    /// time so the preview can animate.
+    fn get_noise_3d(&self, x: f64, y: f64, z: f64, size: u32) -> f64 {
+        let mut value = 0.0;
+        let mut frequency = self.seamless_frequency(size);
+        let base_frequency = frequency;
         let mut amplitude = self.noise.gain;
+        let lacunarity = self.effective_lacunarity();
+        let mut lattice = self.noise;
 
         for _ in 0..self.noise.octaves {
-            let sample = match self.noise.noise_type {
-                NoiseType::Value => noise::Value::new(self.seed).get([point[0] * frequency, point[1] * frequency]),
-                NoiseType::Perlin => noise::Perlin::new(self.seed).get([point[0] * frequency, point[1] * frequency]),
-                NoiseType::Simplex => noise::Simplex::new(self.seed).get([point[0] * frequency, point[1] * frequency]),
+            let sample = match lattice.noise_type {
+                NoiseType::Value => lattice.value_3d(x * frequency, y * frequency, z * frequency),
+                NoiseType::Perlin => lattice.perlin_3d(x * frequency, y * frequency, z * frequency),
+                NoiseType::Simplex => lattice.simplex_3d(x * frequency, y * frequency, z * frequency),
+                NoiseType::ImprovedPerlin => {
+                    lattice.improved_perlin_3d(x * frequency, y * frequency, z * frequency)
+                }
             };
-            value += sample * amplitude;
-            frequency *= self.noise.lacunarity;
+            match self.fractal_mode {
+                FractalMode::Fractal => value += sample * amplitude,
+                FractalMode::Turbulence => value += sample.abs() * amplitude,
+            }
+            frequency *= lacunarity;
             amplitude *= self.noise.persistence;
+            if let Some((px, py)) = self.noise.tile {
+                let scale = (frequency / base_frequency).round().max(1.0) as i32;
+                lattice.tile = Some((px.saturating_mul(scale).min(256), py.saturating_mul(scale).min(256)));
+            }
+        }
+
+        match self.fractal_mode {
+            FractalMode::Fractal => (value + 1.0) / 2.0,
+            FractalMode::Turbulence => value,
         }
-        value
     }
 
     fn export_wallpaper(&self) {
         let size = 1920;
-        let mut image = ImageBuffer::new(size, size);
+        let mut samples = vec![[0u8; 3]; (size * size) as usize];
 
-        for (x, y, pixel) in image.enumerate_pixels_mut() {
-            let value = self.get_noise(x as f64, y as f64);
-            let color = ((value + 1.0) / 2.0 * 255.0) as u8;
-            *pixel = Rgba([color, color, color, 255]);
+        for y in 0..size {
+            for x in 0..size {
+                let value = self.get_noise(x as f64, y as f64, size);
+                samples[(y * size + x) as usize] = self.colormap.sample(value);
+            }
+        }
+
+        if self.quantize {
+            let (palette, indices) = palette::median_cut(&samples, self.palette_size as usize);
+            palette::save_indexed_png("wallpaper.png", size, size, &indices, &palette).unwrap();
+        } else {
+            let mut image = ImageBuffer::new(size, size);
+            for (x, y, pixel) in image.enumerate_pixels_mut() {
+                let [r, g, b] = samples[(y * size + x) as usize];
+                *pixel = Rgba([r, g, b, 255]);
+            }
+            image.save("wallpaper.png").unwrap();
         }
+    }
 
-        image.save("wallpaper.png").unwrap();
+    /// Renders `loop_frames` frames sampling the time axis on a circle of
+    /// `loop_radius`, so frame 0 and frame `loop_frames` land on the same
+    /// `(z, w)` and the sequence loops seamlessly. `w` is fed in as a shift
+    /// of the x coordinate, since the crate's noise only goes up to 3D.
+    fn export_loop(&self) {
+        let size = 512;
+
+        for frame in 0..self.loop_frames {
+            let t = frame as f64 / self.loop_frames as f64 * self.loop_period;
+            let angle = 2.0 * std::f64::consts::PI * t / self.loop_period;
+            let z = self.loop_radius * angle.cos();
+            let w = self.loop_radius * angle.sin();
+
+            let mut image = ImageBuffer::new(size, size);
+            for (x, y, pixel) in image.enumerate_pixels_mut() {
+                let value = self.get_noise_3d(x as f64 + w, y as f64, z, size);
+                let [r, g, b] = self.colormap.sample(value);
+                *pixel = Rgba([r, g, b, 255]);
+            }
+
+            image.save(format!("frame_{:04}.png", frame)).unwrap();
+        }
     }
 }
@@ -1,5 +1,7 @@
 mod noise;
 mod gui;
+mod colormap;
+mod palette;
 
 use gui::NoiseGui;
 use eframe::epi::App;
@@ -0,0 +1,78 @@
+use crate::noise::Noise;
+
+impl Noise {
+    pub fn improved_perlin(&self, x: f64, y: f64) -> f64 {
+        let i = x.floor() as i32;
+        let j = y.floor() as i32;
+
+        let x = x - i as f64;
+        let y = y - j as f64;
+
+        let i = i & 255;
+        let j = j & 255;
+
+        let gll = self.perm[i as usize + self.perm[j as usize] as usize];
+        let glh = self.perm[i as usize + self.perm[(j + 1) as usize] as usize];
+        let ghl = self.perm[(i + 1) as usize + self.perm[j as usize] as usize];
+        let ghh = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize] as usize];
+
+        let nll = self.improved_grad(gll, x, y, 0.0);
+        let nlh = self.improved_grad(glh, x, y - 1.0, 0.0);
+        let nhl = self.improved_grad(ghl, x - 1.0, y, 0.0);
+        let nhh = self.improved_grad(ghh, x - 1.0, y - 1.0, 0.0);
+
+        let u = self.fade(x);
+        let v = self.fade(y);
+
+        let nyl = nll + ((nhl - nll) * u);
+        let nyh = nlh + ((nhh - nlh) * u);
+
+        nyl + ((nyh - nyl) * v)
+    }
+
+    pub fn improved_perlin_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        let i = x.floor() as i32;
+        let j = y.floor() as i32;
+        let k = z.floor() as i32;
+
+        let x = x - i as f64;
+        let y = y - j as f64;
+        let z = z - k as f64;
+
+        let i = i & 255;
+        let j = j & 255;
+        let k = k & 255;
+
+        let glll = self.perm[i as usize + self.perm[j as usize + self.perm[k as usize] as usize] as usize];
+        let glhl = self.perm[i as usize + self.perm[(j + 1) as usize + self.perm[k as usize] as usize] as usize];
+        let ghll = self.perm[(i + 1) as usize + self.perm[j as usize + self.perm[k as usize] as usize] as usize];
+        let ghhl = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize + self.perm[k as usize] as usize] as usize];
+        let gllh = self.perm[i as usize + self.perm[j as usize + self.perm[(k + 1) as usize] as usize] as usize];
+        let glhh = self.perm[i as usize + self.perm[(j + 1) as usize + self.perm[(k + 1) as usize] as usize] as usize];
+        let ghlh = self.perm[(i + 1) as usize + self.perm[j as usize + self.perm[(k + 1) as usize] as usize] as usize];
+        let ghhh = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize + self.perm[(k + 1) as usize] as usize] as usize];
+
+        let nlll = self.improved_grad(glll, x, y, z);
+        let nlhl = self.improved_grad(glhl, x, y - 1.0, z);
+        let nhll = self.improved_grad(ghll, x - 1.0, y, z);
+        let nhhl = self.improved_grad(ghhl, x - 1.0, y - 1.0, z);
+        let nllh = self.improved_grad(gllh, x, y, z - 1.0);
+        let nlhh = self.improved_grad(glhh, x, y - 1.0, z - 1.0);
+        let nhlh = self.improved_grad(ghlh, x - 1.0, y, z - 1.0);
+        let nhhh = self.improved_grad(ghhh, x - 1.0, y - 1.0, z - 1.0);
+
+        let u = self.fade(x);
+        let v = self.fade(y);
+        let w = self.fade(z);
+
+        let nxll = nlll + ((nhll - nlll) * u);
+        let nxlh = nllh + ((nhlh - nllh) * u);
+        let nxhl = nlhl + ((nhhl - nlhl) * u);
+        let nxhh = nlhh + ((nhhh - nlhh) * u);
+
+        let nxyl = nxll + ((nxhl - nxll) * v);
+        let nxyh = nxlh + ((nxhh - nxlh) * v);
+
+        nxyl + ((nxyh - nxyl) * w)
+    }
+}
@@ -1,6 +1,7 @@
 pub mod value_noise;
 pub mod perlin_noise;
 pub mod simplex_noise;
+pub mod improved_perlin;
 
 use std::default::Default;
 
@@ -9,6 +10,7 @@ pub enum NoiseType {
     Value,
     Perlin,
     Simplex,
+    ImprovedPerlin,
 }
 
 impl Default for NoiseType {
@@ -25,6 +27,19 @@ pub struct Noise {
     pub lacunarity: f64,
     pub persistence: f64,
     pub gain: f64,
+    /// Tile period `(x, y)` in integer lattice cells. When set, `perlin`,
+    /// `perlin_3d`, `value`, and `value_3d` wrap lattice lookups so the
+    /// noise repeats seamlessly every `x`/`y` cells (the z axis of the 3D
+    /// variants reuses the x period). `None` disables tiling. Periods past
+    /// 256 are clamped by `wrap_axis`, since `perm` only has 256 distinct
+    /// entries.
+    ///
+    /// This field is per-octave: multi-octave callers (see
+    /// `NoiseGui::get_noise`) must scale it by `lacunarity.powi(octave)`
+    /// each step, since frequency scales the same way, or the wrap boundary
+    /// drifts off the image edge and higher octaves seam. That scaling only
+    /// lands on whole lattice cells when `lacunarity` is a whole number.
+    pub tile: Option<(i32, i32)>,
     perm: [i32; 512],
     grad3: [[f64; 3]; 12],
 }
@@ -60,6 +75,7 @@ impl Default for Noise {
             lacunarity: 1.9,
             persistence: 1.8,
             gain: 0.33,
+            tile: None,
             perm,
             grad3: [
                 [1.0, 1.0, 0.0],
@@ -98,10 +114,80 @@ impl Noise {
         noise
     }
 
+    /// Builds a `Noise` whose permutation table is shuffled from `seed`,
+    /// so that `perlin`/`simplex`/`value` actually vary with the seed
+    /// instead of always using the hardcoded table from `default()`.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut noise = Self::default();
+        noise.reseed(seed);
+        noise
+    }
+
+    /// Reshuffles the permutation table from `seed`, keeping every other
+    /// parameter as-is.
+    pub fn reseed(&mut self, seed: u64) {
+        self.perm = Self::shuffled_perm(seed);
+    }
+
+    fn shuffled_perm(seed: u64) -> [i32; 512] {
+        let mut table = [0i32; 256];
+        for (i, slot) in table.iter_mut().enumerate() {
+            *slot = i as i32;
+        }
+
+        let mut state = seed;
+        for i in (1..256).rev() {
+            let r = Self::split_mix64(&mut state);
+            let j = (r % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        let mut perm = [0; 512];
+        for i in 0..512 {
+            perm[i] = table[i & 255];
+        }
+        perm
+    }
+
+    /// SplitMix64: a small, fast, deterministic PRNG used only to drive the
+    /// Fisher-Yates shuffle above.
+    fn split_mix64(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
     fn fade(&self, t: f64) -> f64 {
         t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
     }
 
+    /// Wraps a lattice coordinate's two corners (`i` and `i + 1`) for
+    /// indexing `perm`. With a tile `period` set, each corner is wrapped
+    /// *independently* modulo the period so that `x = 0` and `x = period`
+    /// land on the same gradient, which is what makes the noise seamless.
+    /// Without tiling, falls back to the classic `& 255` mask relying on
+    /// the 512-entry `perm` table to cover `i + 1`.
+    ///
+    /// `period` is clamped to `1..=256` regardless of what callers pass in:
+    /// `perm` only has 256 distinct entries, so a period past that can't be
+    /// represented anyway, and callers that scale the period per fBm octave
+    /// (see `NoiseGui::get_noise`) would otherwise walk `i0 + perm[..]` past
+    /// the end of the 512-entry table.
+    fn wrap_axis(&self, i: i32, period: Option<i32>) -> (i32, i32) {
+        match period {
+            Some(period) => {
+                let period = period.clamp(1, 256);
+                (i.rem_euclid(period), (i + 1).rem_euclid(period))
+            }
+            None => {
+                let i0 = i & 255;
+                (i0, i0 + 1)
+            }
+        }
+    }
+
     fn grad_dot(&self, hash: i32, x: f64, y: f64) -> f64 {
         let grad = self.grad3[hash as usize];
         grad[0] * x + grad[1] * y
@@ -111,4 +197,21 @@ impl Noise {
         let grad = self.grad3[hash as usize];
         grad[0] * x + grad[1] * y + grad[2] * z
     }
+
+    /// Ken Perlin's 2002 "improved noise" gradient: folds the low 4 bits of
+    /// `hash` into one of 12 edge-midpoint directions without a lookup
+    /// table, avoiding the directional clumping of the classic `grad3` set.
+    /// Pass `z = 0.0` for the 2D case.
+    fn improved_grad(&self, hash: i32, x: f64, y: f64, z: f64) -> f64 {
+        let h = hash & 15;
+        let u = if h < 8 { x } else { y };
+        let v = if h < 4 {
+            y
+        } else if h == 12 || h == 14 {
+            x
+        } else {
+            z
+        };
+        (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+    }
 }
@@ -8,13 +8,17 @@ impl Noise {
         let x = x - i as f64;
         let y = y - j as f64;
 
-        let i = i & 255;
-        let j = j & 255;
-
-        let gll = self.perm[i as usize + self.perm[j as usize] as usize] % 12;
-        let glh = self.perm[i as usize + self.perm[(j + 1) as usize] as usize] % 12;
-        let ghl = self.perm[(i + 1) as usize + self.perm[j as usize] as usize] % 12;
-        let ghh = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize] as usize] % 12;
+        let (px, py) = match self.tile {
+            Some((px, py)) => (Some(px), Some(py)),
+            None => (None, None),
+        };
+        let (i0, i1) = self.wrap_axis(i, px);
+        let (j0, j1) = self.wrap_axis(j, py);
+
+        let gll = self.perm[i0 as usize + self.perm[j0 as usize] as usize] % 12;
+        let glh = self.perm[i0 as usize + self.perm[j1 as usize] as usize] % 12;
+        let ghl = self.perm[i1 as usize + self.perm[j0 as usize] as usize] % 12;
+        let ghh = self.perm[i1 as usize + self.perm[j1 as usize] as usize] % 12;
 
         let nll = self.grad_dot(gll, x, y);
         let nlh = self.grad_dot(glh, x, y - 1.0);
@@ -41,18 +45,23 @@ impl Noise {
         let y = y - j as f64;
         let z = z - k as f64;
 
-        let i = i & 255;
-        let j = j & 255;
-        let k = k & 255;
-
-        let glll = self.perm[i as usize + self.perm[j as usize + self.perm[k as usize] as usize] as usize] % 12;
-        let glhl = self.perm[i as usize + self.perm[(j + 1) as usize + self.perm[k as usize] as usize] as usize] % 12;
-        let ghll = self.perm[(i + 1) as usize + self.perm[j as usize + self.perm[k as usize] as usize] as usize] % 12;
-        let ghhl = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize + self.perm[k as usize] as usize] as usize] % 12;
-        let gllh = self.perm[i as usize + self.perm[j as usize + self.perm[(k + 1) as usize] as usize] as usize] % 12;
-        let glhh = self.perm[i as usize + self.perm[(j + 1) as usize + self.perm[(k + 1) as usize] as usize] as usize] % 12;
-        let ghlh = self.perm[(i + 1) as usize + self.perm[j as usize + self.perm[(k + 1) as usize] as usize] as usize] % 12;
-        let ghhh = self.perm[(i + 1) as usize + self.perm[(j + 1) as usize + self.perm[(k + 1) as usize] as usize] as usize] % 12;
+        let (px, py) = match self.tile {
+            Some((px, py)) => (Some(px), Some(py)),
+            None => (None, None),
+        };
+        // The z axis reuses the x tile period, since `tile` only models a 2D pattern.
+        let (i0, i1) = self.wrap_axis(i, px);
+        let (j0, j1) = self.wrap_axis(j, py);
+        let (k0, k1) = self.wrap_axis(k, px);
+
+        let glll = self.perm[i0 as usize + self.perm[j0 as usize + self.perm[k0 as usize] as usize] as usize] % 12;
+        let glhl = self.perm[i0 as usize + self.perm[j1 as usize + self.perm[k0 as usize] as usize] as usize] % 12;
+        let ghll = self.perm[i1 as usize + self.perm[j0 as usize + self.perm[k0 as usize] as usize] as usize] % 12;
+        let ghhl = self.perm[i1 as usize + self.perm[j1 as usize + self.perm[k0 as usize] as usize] as usize] % 12;
+        let gllh = self.perm[i0 as usize + self.perm[j0 as usize + self.perm[k1 as usize] as usize] as usize] % 12;
+        let glhh = self.perm[i0 as usize + self.perm[j1 as usize + self.perm[k1 as usize] as usize] as usize] % 12;
+        let ghlh = self.perm[i1 as usize + self.perm[j0 as usize + self.perm[k1 as usize] as usize] as usize] % 12;
+        let ghhh = self.perm[i1 as usize + self.perm[j1 as usize + self.perm[k1 as usize] as usize] as usize] % 12;
 
         let nlll = self.grad_dot_3d(glll, x, y, z);
         let nlhl = self.grad_dot_3d(glhl, x, y - 1.0, z);
@@ -5,13 +5,17 @@ impl Noise {
         let i = x.floor() as i32;
         let j = y.floor() as i32;
 
-        let ii = i & 255;
-        let jj = j & 255;
+        let (px, py) = match self.tile {
+            Some((px, py)) => (Some(px), Some(py)),
+            None => (None, None),
+        };
+        let (ii0, ii1) = self.wrap_axis(i, px);
+        let (jj0, jj1) = self.wrap_axis(j, py);
 
-        let nll = self.perm[ii as usize + self.perm[jj as usize] as usize] as f64 / 255.0;
-        let nhl = self.perm[ii as usize + self.perm[(jj + 1) as usize] as usize] as f64 / 255.0;
-        let nlh = self.perm[(ii + 1) as usize + self.perm[jj as usize] as usize] as f64 / 255.0;
-        let nhh = self.perm[(ii + 1) as usize + self.perm[(jj + 1) as usize] as usize] as f64 / 255.0;
+        let nll = self.perm[ii0 as usize + self.perm[jj0 as usize] as usize] as f64 / 255.0;
+        let nhl = self.perm[ii0 as usize + self.perm[jj1 as usize] as usize] as f64 / 255.0;
+        let nlh = self.perm[ii1 as usize + self.perm[jj0 as usize] as usize] as f64 / 255.0;
+        let nhh = self.perm[ii1 as usize + self.perm[jj1 as usize] as usize] as f64 / 255.0;
 
         let u = x - i as f64;
         let v = y - j as f64;
@@ -29,18 +33,24 @@ impl Noise {
         let j = y.floor() as i32;
         let k = z.floor() as i32;
 
-        let ii = i & 255;
-        let jj = j & 255;
-        let kk = k & 255;
+        let (px, py) = match self.tile {
+            Some((px, py)) => (Some(px), Some(py)),
+            None => (None, None),
+        };
+        // The z axis reuses the x tile period, same as `perlin_3d`, since
+        // `tile` only models a 2D pattern.
+        let (ii, ii1) = self.wrap_axis(i, px);
+        let (jj, jj1) = self.wrap_axis(j, py);
+        let (kk, kk1) = self.wrap_axis(k, px);
 
         let nlll = self.perm[ii as usize + self.perm[jj as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
-        let nlhl = self.perm[ii as usize + self.perm[(jj + 1) as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
-        let nhll = self.perm[(ii + 1) as usize + self.perm[jj as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
-        let nhhl = self.perm[(ii + 1) as usize + self.perm[(jj + 1) as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
-        let nllh = self.perm[ii as usize + self.perm[jj as usize + self.perm[(kk + 1) as usize] as usize] as usize] as f64 / 255.0;
-        let nlhh = self.perm[ii as usize + self.perm[(jj + 1) as usize + self.perm[(kk + 1) as usize] as usize] as usize] as f64 / 255.0;
-        let nhlh = self.perm[(ii + 1) as usize + self.perm[jj as usize + self.perm[(kk + 1) as usize] as usize] as usize] as f64 / 255.0;
-        let nhhh = self.perm[(ii + 1) as usize + self.perm[(jj + 1) as usize + self.perm[(kk + 1) as usize] as usize] as usize] as f64 / 255.0;
+        let nlhl = self.perm[ii as usize + self.perm[jj1 as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
+        let nhll = self.perm[ii1 as usize + self.perm[jj as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
+        let nhhl = self.perm[ii1 as usize + self.perm[jj1 as usize + self.perm[kk as usize] as usize] as usize] as f64 / 255.0;
+        let nllh = self.perm[ii as usize + self.perm[jj as usize + self.perm[kk1 as usize] as usize] as usize] as f64 / 255.0;
+        let nlhh = self.perm[ii as usize + self.perm[jj1 as usize + self.perm[kk1 as usize] as usize] as usize] as f64 / 255.0;
+        let nhlh = self.perm[ii1 as usize + self.perm[jj as usize + self.perm[kk1 as usize] as usize] as usize] as f64 / 255.0;
+        let nhhh = self.perm[ii1 as usize + self.perm[jj1 as usize + self.perm[kk1 as usize] as usize] as usize] as f64 / 255.0;
 
         let u = x - i as f64;
         let v = y - j as f64;
@@ -0,0 +1,192 @@
+/// A box of sample indices in median-cut quantization, split recursively
+/// along its widest channel until the target color count is reached.
+struct Bucket {
+    indices: Vec<usize>,
+}
+
+impl Bucket {
+    fn channel_range(&self, samples: &[[u8; 3]], channel: usize) -> (u8, u8) {
+        let mut lo = 255u8;
+        let mut hi = 0u8;
+        for &i in &self.indices {
+            let v = samples[i][channel];
+            lo = lo.min(v);
+            hi = hi.max(v);
+        }
+        (lo, hi)
+    }
+
+    fn widest_channel(&self, samples: &[[u8; 3]]) -> usize {
+        (0..3).max_by_key(|&c| self.channel_spread(samples, c)).unwrap()
+    }
+
+    fn channel_spread(&self, samples: &[[u8; 3]], channel: usize) -> i32 {
+        let (lo, hi) = self.channel_range(samples, channel);
+        hi as i32 - lo as i32
+    }
+
+    /// The spread of this bucket's widest channel — its "volume" for the
+    /// purposes of picking which bucket to split next.
+    fn spread(&self, samples: &[[u8; 3]]) -> i32 {
+        (0..3).map(|c| self.channel_spread(samples, c)).max().unwrap()
+    }
+
+    fn average(&self, samples: &[[u8; 3]]) -> [u8; 3] {
+        let n = self.indices.len().max(1) as u64;
+        let mut sum = [0u64; 3];
+        for &i in &self.indices {
+            for (c, channel_sum) in sum.iter_mut().enumerate() {
+                *channel_sum += samples[i][c] as u64;
+            }
+        }
+        [
+            (sum[0] / n) as u8,
+            (sum[1] / n) as u8,
+            (sum[2] / n) as u8,
+        ]
+    }
+}
+
+const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+/// Writes an indexed PNG from per-pixel palette indices. `image`'s
+/// `ImageBuffer`/`save` path only knows how to write truecolor images, and
+/// the `png` crate is merely a transitive dependency of `image` (not
+/// importable without its own manifest entry), so this hand-rolls the
+/// handful of PNG chunks we need instead of relying on either.
+pub fn save_indexed_png(
+    path: &str,
+    width: u32,
+    height: u32,
+    indices: &[u8],
+    palette: &[[u8; 3]],
+) -> std::io::Result<()> {
+    let mut png = Vec::with_capacity(PNG_SIGNATURE.len() + indices.len() + palette.len() * 3 + 64);
+    png.extend_from_slice(&PNG_SIGNATURE);
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 3, 0, 0, 0]); // 8-bit depth, color type 3 (indexed)
+    write_chunk(&mut png, b"IHDR", &ihdr);
+
+    let plte: Vec<u8> = palette.iter().flat_map(|c| c.iter().copied()).collect();
+    write_chunk(&mut png, b"PLTE", &plte);
+
+    let mut scanlines = Vec::with_capacity(indices.len() + height as usize);
+    for row in indices.chunks(width as usize) {
+        scanlines.push(0); // filter type: None
+        scanlines.extend_from_slice(row);
+    }
+    write_chunk(&mut png, b"IDAT", &zlib_store(&scanlines));
+
+    write_chunk(&mut png, b"IEND", &[]);
+
+    std::fs::write(path, png)
+}
+
+fn write_chunk(out: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(chunk_type);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc32(&out[start..]).to_be_bytes());
+}
+
+/// Wraps `data` in a minimal zlib stream made of uncompressed ("stored")
+/// DEFLATE blocks. These textures are small enough that skipping real
+/// compression is an acceptable trade for not needing a deflate crate.
+fn zlib_store(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+
+    let mut out = Vec::with_capacity(data.len() + 6 * (data.len() / MAX_BLOCK + 1) + 6);
+    out.extend_from_slice(&[0x78, 0x01]); // zlib header: deflate, fastest
+
+    let mut chunks = data.chunks(MAX_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        // Even empty input needs one final (empty) stored block.
+        write_stored_block(&mut out, &[], true);
+    }
+    while let Some(chunk) = chunks.next() {
+        write_stored_block(&mut out, chunk, chunks.peek().is_none());
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn write_stored_block(out: &mut Vec<u8>, chunk: &[u8], is_final: bool) {
+    out.push(if is_final { 0x01 } else { 0x00 });
+    let len = chunk.len() as u16;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(&(!len).to_le_bytes());
+    out.extend_from_slice(chunk);
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for &byte in data {
+        a = (a + byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+/// Median-cut color quantization: reduces `samples` to at most
+/// `target_colors` (clamped to `1..=256`) representative colors. Returns the
+/// palette and, for each input sample, the index of its palette entry.
+pub fn median_cut(samples: &[[u8; 3]], target_colors: usize) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let target_colors = target_colors.clamp(1, 256);
+
+    let mut buckets = vec![Bucket {
+        indices: (0..samples.len()).collect(),
+    }];
+
+    while buckets.len() < target_colors {
+        let split_at = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.indices.len() > 1)
+            .max_by_key(|(_, b)| b.spread(samples))
+            .map(|(i, _)| i);
+
+        let split_at = match split_at {
+            Some(i) => i,
+            None => break,
+        };
+
+        let mut bucket = buckets.swap_remove(split_at);
+        let channel = bucket.widest_channel(samples);
+        bucket.indices.sort_by_key(|&i| samples[i][channel]);
+
+        let mid = bucket.indices.len() / 2;
+        let right = bucket.indices.split_off(mid);
+        buckets.push(bucket);
+        buckets.push(Bucket { indices: right });
+    }
+
+    let palette: Vec<[u8; 3]> = buckets.iter().map(|b| b.average(samples)).collect();
+
+    let mut index_of = vec![0u8; samples.len()];
+    for (bucket_index, bucket) in buckets.iter().enumerate() {
+        for &i in &bucket.indices {
+            index_of[i] = bucket_index as u8;
+        }
+    }
+
+    (palette, index_of)
+}